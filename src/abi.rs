@@ -0,0 +1,43 @@
+// On-chain ABI bindings used to price DEX pools.
+//
+// `abigen!` generates a typed contract wrapper (methods, return types, and
+// event structs) from a minimal ABI fragment, so callers get compile-time
+// checked calls instead of hand-rolled `ethers::abi::Function` lookups.
+use ethers::contract::abigen;
+
+abigen!(
+    IUniswapV2Router02,
+    r#"[
+        function getAmountsOut(uint256 amountIn, address[] calldata path) external view returns (uint256[] memory amounts)
+        function swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] calldata path, address to, uint256 deadline) external returns (uint256[] memory amounts)
+    ]"#,
+);
+
+abigen!(
+    IUniswapV2Pair,
+    r#"[
+        function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast)
+        function token0() external view returns (address)
+    ]"#,
+);
+
+abigen!(
+    IStableSwapPool,
+    r#"[
+        function balances(uint256 i) external view returns (uint256)
+    ]"#,
+);
+
+abigen!(
+    IUniswapV3Quoter,
+    r#"[
+        function quoteExactInputSingle(address tokenIn, address tokenOut, uint24 fee, uint256 amountIn, uint160 sqrtPriceLimitX96) external returns (uint256 amountOut)
+    ]"#,
+);
+
+abigen!(
+    IERC20,
+    r#"[
+        function balanceOf(address account) external view returns (uint256)
+    ]"#,
+);
@@ -0,0 +1,120 @@
+// Constant-product AMM math for Uniswap V2-style pools, plus a solver for
+// the optimal trade size across a two-pool arbitrage cycle.
+use ethers::types::U256;
+
+/// Uniswap V2's 0.3% fee swap formula: `amountOut = (amountIn * 997 * reserveOut)
+/// / (reserveIn * 1000 + amountIn * 997)`. All-integer, matching what the
+/// router contract itself computes.
+pub fn get_amount_out(amount_in: U256, reserve_in: U256, reserve_out: U256) -> U256 {
+    if amount_in.is_zero() || reserve_in.is_zero() || reserve_out.is_zero() {
+        return U256::zero();
+    }
+    let amount_in_with_fee = amount_in * 997;
+    let numerator = amount_in_with_fee * reserve_out;
+    let denominator = reserve_in * 1000 + amount_in_with_fee;
+    numerator / denominator
+}
+
+/// Net profit (in the input token's base units) of spending `amount_in` to
+/// buy on `(reserve_in_buy, reserve_out_buy)` then immediately selling the
+/// proceeds on `(reserve_in_sell, reserve_out_sell)`. Saturates to zero
+/// rather than underflowing if the round trip is a loss.
+fn profit(
+    amount_in: U256,
+    reserve_in_buy: U256,
+    reserve_out_buy: U256,
+    reserve_in_sell: U256,
+    reserve_out_sell: U256,
+) -> U256 {
+    let bought = get_amount_out(amount_in, reserve_in_buy, reserve_out_buy);
+    let sold = get_amount_out(bought, reserve_in_sell, reserve_out_sell);
+    sold.saturating_sub(amount_in)
+}
+
+/// The result of solving for the best trade size over a two-pool cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptimalTrade {
+    pub amount_in: U256,
+    pub net_profit: U256,
+}
+
+/// Finds the input amount that maximizes `profit(x)` over `[0, reserve_in_buy]`
+/// via ternary search. `profit` is unimodal here (rises then falls as slippage
+/// eats the spread), so narrowing the window by discarding a third on each
+/// iteration converges to the optimum without needing calculus on `U256`.
+pub fn find_optimal_trade(
+    reserve_in_buy: U256,
+    reserve_out_buy: U256,
+    reserve_in_sell: U256,
+    reserve_out_sell: U256,
+) -> OptimalTrade {
+    let mut lo = U256::zero();
+    let mut hi = reserve_in_buy;
+
+    let profit_at = |x: U256| -> U256 {
+        profit(x, reserve_in_buy, reserve_out_buy, reserve_in_sell, reserve_out_sell)
+    };
+
+    // Stop once the window is too narrow to matter; one unit is the floor.
+    while hi - lo > U256::from(1) {
+        let third = (hi - lo) / 3;
+        let m1 = lo + third;
+        let m2 = hi - third;
+
+        if profit_at(m1) < profit_at(m2) {
+            lo = m1 + 1;
+        } else {
+            hi = m2 - 1;
+        }
+    }
+
+    let best_amount = if profit_at(lo) >= profit_at(hi) { lo } else { hi };
+    OptimalTrade {
+        amount_in: best_amount,
+        net_profit: profit_at(best_amount),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_amount_out_matches_uniswap_v2_formula() {
+        // 1,000 WETH / 2,000,000 USDC pool, swap in 1 WETH.
+        let reserve_in = U256::from(1_000u64) * U256::exp10(18);
+        let reserve_out = U256::from(2_000_000u64) * U256::exp10(6);
+        let amount_in = U256::exp10(18);
+        let amount_out = get_amount_out(amount_in, reserve_in, reserve_out);
+        // (1e18 * 997 * 2_000_000e6) / (1_000e18 * 1000 + 1e18 * 997)
+        assert_eq!(amount_out, U256::from(1_992_013_962u64));
+    }
+
+    #[test]
+    fn find_optimal_trade_prefers_zero_when_cycle_is_unprofitable() {
+        // Identical reserves on both legs, so any round trip just bleeds fees.
+        let reserve_in = U256::from(1_000u64) * U256::exp10(18);
+        let reserve_out = U256::from(2_000_000u64) * U256::exp10(6);
+        let trade = find_optimal_trade(reserve_out, reserve_in, reserve_in, reserve_out);
+        assert_eq!(trade.net_profit, U256::zero());
+    }
+
+    #[test]
+    fn find_optimal_trade_finds_profit_when_cheap_pool_is_underpriced() {
+        // Buy WETH cheap on pool A (1,000 WETH / 1,800,000 USDC), sell it on
+        // pool B at the "real" price (1,000 WETH / 2,000,000 USDC).
+        let reserve_usdc_cheap = U256::from(1_800_000u64) * U256::exp10(6);
+        let reserve_weth_cheap = U256::from(1_000u64) * U256::exp10(18);
+        let reserve_weth_expensive = U256::from(1_000u64) * U256::exp10(18);
+        let reserve_usdc_expensive = U256::from(2_000_000u64) * U256::exp10(6);
+
+        let trade = find_optimal_trade(
+            reserve_usdc_cheap,
+            reserve_weth_cheap,
+            reserve_weth_expensive,
+            reserve_usdc_expensive,
+        );
+        assert!(trade.amount_in > U256::zero());
+        assert!(trade.net_profit > U256::zero());
+    }
+}
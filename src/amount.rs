@@ -0,0 +1,63 @@
+// A raw on-chain amount, always expressed in a token's base units (wei-like,
+// not a human-readable decimal). `config.json` can spell one out either as a
+// `0x`-prefixed hex string or a plain decimal string, since hand-converting
+// large WETH/USDC amounts to hex is error-prone.
+use ethers::types::U256;
+use serde::{Deserialize, Deserializer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Amount(pub U256);
+
+impl Amount {
+    pub fn as_u256(self) -> U256 {
+        self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        let value = match raw.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16),
+            None => U256::from_dec_str(&raw),
+        }
+        .map_err(|e| serde::de::Error::custom(format!("invalid amount {raw:?}: {e}")))?;
+        Ok(Amount(value))
+    }
+}
+
+/// Converts a base-units amount to a human-readable `f64`, for display and
+/// for threshold comparisons only -- the arithmetic itself should stay in
+/// `U256` for as long as possible to match what the on-chain contracts
+/// actually compute.
+pub fn to_display_f64(amount: U256, decimals: u32) -> f64 {
+    let scale = U256::exp10(decimals as usize);
+    amount.as_u128() as f64 / scale.as_u128() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_hex_amount() {
+        let amount: Amount = serde_json::from_value(json!("0x2386f26fc10000")).unwrap();
+        assert_eq!(amount.as_u256(), U256::from(10_000_000_000_000_000u64));
+    }
+
+    #[test]
+    fn parses_decimal_amount() {
+        let amount: Amount = serde_json::from_value(json!("10000000000000000")).unwrap();
+        assert_eq!(amount.as_u256(), U256::from(10_000_000_000_000_000u64));
+    }
+
+    #[test]
+    fn rejects_invalid_amount() {
+        let result: Result<Amount, _> = serde_json::from_value(json!("not a number"));
+        assert!(result.is_err());
+    }
+}
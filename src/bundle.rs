@@ -0,0 +1,63 @@
+// Atomic bundle submission to a private relay, following the MEV bundle RPC
+// pattern (`eth_sendBundle` with signed raw transactions, a target block
+// number, and a bundle signature header) so the buy and sell legs land
+// together in the same block or not at all. This closes the window a
+// sequential public-mempool submission leaves open for another searcher to
+// capture the spread between the two legs.
+use ethers::core::utils::keccak256;
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::{Bytes, U64};
+use serde_json::json;
+
+/// Signs the bundle body with `searcher_wallet` and POSTs it to
+/// `bundle_rpc_url` as `eth_sendBundle`, targeting `target_block`.
+pub async fn send_bundle(
+    bundle_rpc_url: &str,
+    searcher_wallet: &LocalWallet,
+    raw_txs: Vec<Bytes>,
+    target_block: U64,
+) -> Result<(), anyhow::Error> {
+    let raw_tx_hexes: Vec<String> = raw_txs.iter().map(|tx| tx.to_string()).collect();
+
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_sendBundle",
+        "params": [{
+            "txs": raw_tx_hexes,
+            "blockNumber": format!("0x{:x}", target_block.as_u64()),
+        }],
+    });
+    let body_str = body.to_string();
+
+    // Relays authenticate the searcher by a header of the form
+    // `<address>:<signature>`, where the signature is over the personal-sign
+    // hash of the request body's keccak256 digest.
+    let digest = format!("0x{}", hex::encode(keccak256(body_str.as_bytes())));
+    let signature = searcher_wallet.sign_message(digest).await?;
+    let signature_header = format!("{:?}:0x{}", searcher_wallet.address(), signature);
+
+    let response = reqwest::Client::new()
+        .post(bundle_rpc_url)
+        .header("Content-Type", "application/json")
+        .header("X-Bundle-Signature", signature_header)
+        .body(body_str)
+        .send()
+        .await?;
+
+    let status = response.status();
+    let response_body = response.text().await?;
+    if !status.is_success() {
+        return Err(anyhow::anyhow!(
+            "bundle relay returned {}: {}",
+            status,
+            response_body
+        ));
+    }
+    if response_body.contains("\"error\"") {
+        return Err(anyhow::anyhow!("bundle relay rejected bundle: {}", response_body));
+    }
+
+    println!("Bundle submitted for block {}: {}", target_block, response_body);
+    Ok(())
+}
@@ -0,0 +1,222 @@
+// Opt-in execution of detected arbitrage. Building the client stack here
+// (rather than reusing the read-only provider) mirrors the ethers pattern of
+// layering a `SignerMiddleware` for transaction signing under a
+// `NonceManagerMiddleware` so rapid back-to-back submissions track the
+// account's nonce locally instead of racing `eth_getTransactionCount`.
+use crate::abi::{IERC20, IUniswapV2Router02};
+use crate::bundle;
+use ethers::middleware::{NonceManagerMiddleware, SignerMiddleware};
+use ethers::providers::Middleware;
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::{Address, U256};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub type ExecutionClient<M> = NonceManagerMiddleware<SignerMiddleware<Arc<M>, LocalWallet>>;
+
+/// Applies a slippage tolerance (in basis points, e.g. 50 = 0.5%) to a quoted
+/// output amount to get the `amountOutMin` floor passed to the router. A
+/// stale quote can still cost up to the tolerance, but never the whole trade.
+fn apply_slippage(quoted_amount_out: U256, slippage_tolerance_bps: u64) -> U256 {
+    quoted_amount_out * U256::from(10_000u64 - slippage_tolerance_bps.min(10_000)) / U256::from(10_000u64)
+}
+
+/// Builds the signing + nonce-managing client stack used to submit live
+/// trades. The private key is read from `PRIVATE_KEY`, never from
+/// `config.json`, so it can't end up committed alongside the rest of the
+/// bot's settings.
+pub async fn build_execution_client<M: Middleware + 'static>(
+    provider: Arc<M>,
+    chain_id: u64,
+) -> Result<Arc<ExecutionClient<M>>, anyhow::Error> {
+    let private_key = std::env::var("PRIVATE_KEY")
+        .map_err(|_| anyhow::anyhow!("PRIVATE_KEY env var must be set to run with --live"))?;
+    let wallet = private_key.parse::<LocalWallet>()?.with_chain_id(chain_id);
+    let address = wallet.address();
+
+    let signer = SignerMiddleware::new(provider, wallet);
+    Ok(Arc::new(NonceManagerMiddleware::new(signer, address)))
+}
+
+/// Submits the buy and sell legs of a V2 arbitrage sequentially through the
+/// execution client, waiting for each receipt before logging realized vs.
+/// expected profit. Token approvals to the routers are assumed to already be
+/// in place.
+pub async fn execute_v2_arbitrage<M: Middleware + 'static>(
+    client: &Arc<ExecutionClient<M>>,
+    buy_router_address: Address,
+    sell_router_address: Address,
+    token_in: Address,  // USDC, spent on the buy leg
+    token_mid: Address, // WETH, bought then sold
+    amount_in: U256,
+    expected_net_profit: U256,
+    slippage_tolerance_bps: u64,
+) -> Result<(), anyhow::Error> {
+    let deadline = swap_deadline()?;
+    let to = client.inner().address();
+    let token_mid_contract = IERC20::new(token_mid, client.clone());
+    let token_in_contract = IERC20::new(token_in, client.clone());
+    let balance_in_before = token_in_contract.balance_of(to).call().await?;
+
+    let buy_router = IUniswapV2Router02::new(buy_router_address, client.clone());
+    let quoted_mid = buy_router
+        .get_amounts_out(amount_in, vec![token_in, token_mid])
+        .call()
+        .await?
+        .last()
+        .copied()
+        .unwrap_or_default();
+    let buy_amount_out_min = apply_slippage(quoted_mid, slippage_tolerance_bps);
+
+    let balance_before = token_mid_contract.balance_of(to).call().await?;
+    let buy_receipt = buy_router
+        .swap_exact_tokens_for_tokens(amount_in, buy_amount_out_min, vec![token_in, token_mid], to, deadline)
+        .send()
+        .await?
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("buy swap dropped before confirmation"))?;
+    println!("Buy leg confirmed in tx {:?}", buy_receipt.transaction_hash);
+
+    // The buy leg's own quote is stale by the time it confirms, and
+    // re-quoting now would price a *fresh* trade against the pool's
+    // post-trade reserves, not the proceeds we actually received. Read the
+    // real amount from the balance change instead.
+    let balance_after = token_mid_contract.balance_of(to).call().await?;
+    let amount_mid = balance_after.saturating_sub(balance_before);
+
+    let sell_router = IUniswapV2Router02::new(sell_router_address, client.clone());
+    let quoted_out = sell_router
+        .get_amounts_out(amount_mid, vec![token_mid, token_in])
+        .call()
+        .await?
+        .last()
+        .copied()
+        .unwrap_or_default();
+    let sell_amount_out_min = apply_slippage(quoted_out, slippage_tolerance_bps);
+    let sell_receipt = sell_router
+        .swap_exact_tokens_for_tokens(amount_mid, sell_amount_out_min, vec![token_mid, token_in], to, deadline)
+        .send()
+        .await?
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("sell swap dropped before confirmation"))?;
+    println!("Sell leg confirmed in tx {:?}", sell_receipt.transaction_hash);
+
+    let balance_in_after = token_in_contract.balance_of(to).call().await?;
+    if balance_in_after >= balance_in_before {
+        println!(
+            "Realized net profit: {} base units (expected {})",
+            balance_in_after - balance_in_before,
+            expected_net_profit
+        );
+    } else {
+        println!(
+            "Realized net loss: {} base units (expected profit {})",
+            balance_in_before - balance_in_after,
+            expected_net_profit
+        );
+    }
+    Ok(())
+}
+
+/// Submits the buy and sell legs of a V2 arbitrage as a single atomic bundle
+/// to `bundle_rpc_url`, targeting the next block. Both legs sign with
+/// sequential nonces but neither is broadcast individually, so there's no
+/// public-mempool window between them for another searcher to front-run.
+pub async fn execute_v2_arbitrage_bundle<M: Middleware + 'static>(
+    client: &Arc<ExecutionClient<M>>,
+    bundle_rpc_url: &str,
+    buy_router_address: Address,
+    sell_router_address: Address,
+    token_in: Address,
+    token_mid: Address,
+    amount_in: U256,
+    slippage_tolerance_bps: u64,
+) -> Result<(), anyhow::Error> {
+    let deadline = swap_deadline()?;
+    let to = client.inner().address();
+
+    let buy_router = IUniswapV2Router02::new(buy_router_address, client.clone());
+    let amount_mid = buy_router
+        .get_amounts_out(amount_in, vec![token_in, token_mid])
+        .call()
+        .await?
+        .last()
+        .copied()
+        .unwrap_or_default();
+    let buy_amount_out_min = apply_slippage(amount_mid, slippage_tolerance_bps);
+    let sell_router = IUniswapV2Router02::new(sell_router_address, client.clone());
+    let quoted_out = sell_router
+        .get_amounts_out(amount_mid, vec![token_mid, token_in])
+        .call()
+        .await?
+        .last()
+        .copied()
+        .unwrap_or_default();
+    let sell_amount_out_min = apply_slippage(quoted_out, slippage_tolerance_bps);
+
+    let mut buy_tx = buy_router
+        .swap_exact_tokens_for_tokens(amount_in, buy_amount_out_min, vec![token_in, token_mid], to, deadline)
+        .tx;
+    let mut sell_tx = sell_router
+        .swap_exact_tokens_for_tokens(amount_mid, sell_amount_out_min, vec![token_mid, token_in], to, deadline)
+        .tx;
+
+    // Fill (and nonce) both legs through the inner `SignerMiddleware` rather
+    // than `client` itself: `NonceManagerMiddleware` advances its persistent
+    // counter on every `fill_transaction` call whether or not the tx is ever
+    // broadcast, and private bundles routinely never land. Driving a
+    // maybe-never-broadcast speculative leg off that shared counter would
+    // desync it from the real chain nonce and stall every later submission
+    // -- bundled or sequential -- behind the gap. The inner signer instead
+    // re-queries the live pending nonce fresh on each attempt.
+    let signer = client.inner();
+    signer.fill_transaction(&mut buy_tx, None).await?;
+    let buy_nonce = *buy_tx.nonce().ok_or_else(|| anyhow::anyhow!("buy tx missing nonce"))?;
+    signer.fill_transaction(&mut sell_tx, None).await?;
+    sell_tx.set_nonce(buy_nonce + 1);
+
+    let wallet = client.inner().signer();
+    let buy_signature = wallet.sign_transaction(&buy_tx).await?;
+    let sell_signature = wallet.sign_transaction(&sell_tx).await?;
+
+    let raw_txs = vec![
+        buy_tx.rlp_signed(&buy_signature),
+        sell_tx.rlp_signed(&sell_signature),
+    ];
+
+    let target_block = client.get_block_number().await? + 1;
+    bundle::send_bundle(bundle_rpc_url, wallet, raw_txs, target_block).await
+}
+
+fn swap_deadline() -> Result<U256, anyhow::Error> {
+    Ok(U256::from(
+        SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 120,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_slippage_with_zero_tolerance_is_a_no_op() {
+        let quoted = U256::from(1_000_000u64);
+        assert_eq!(apply_slippage(quoted, 0), quoted);
+    }
+
+    #[test]
+    fn apply_slippage_applies_a_normal_tolerance() {
+        // 50 bps = 0.5% off a 1,000,000 quote.
+        let quoted = U256::from(1_000_000u64);
+        assert_eq!(apply_slippage(quoted, 50), U256::from(995_000u64));
+    }
+
+    #[test]
+    fn apply_slippage_clamps_at_full_tolerance() {
+        // >= 10_000 bps (100%) should floor the minimum output at zero
+        // instead of underflowing.
+        let quoted = U256::from(1_000_000u64);
+        assert_eq!(apply_slippage(quoted, 10_000), U256::zero());
+        assert_eq!(apply_slippage(quoted, 20_000), U256::zero());
+    }
+}
@@ -1,7 +1,18 @@
-use ethers::{
-    providers::{Http, Provider},
-    types::{Address, U256},
-};
+mod abi;
+mod amm;
+mod amount;
+mod bundle;
+mod execution;
+mod gas;
+mod stableswap;
+
+use abi::{IStableSwapPool, IUniswapV2Pair, IUniswapV2Router02, IUniswapV3Quoter};
+use amount::Amount;
+use ethers::providers::{Http, Middleware, Provider, Ws};
+use ethers::types::{Address, U256};
+use execution::ExecutionClient;
+use gas::GasOracleClient;
+use futures_util::StreamExt;
 use std::{str::FromStr, sync::Arc};
 use tokio::time::{sleep, Duration};
 use serde::Deserialize;
@@ -11,80 +22,473 @@ use std::fs;
 #[derive(Debug, Deserialize)]
 struct Config {
     rpc_url: String,
+    // When set, the bot subscribes to new block headers over a WebSocket
+    // connection and re-evaluates on every block instead of polling on a
+    // timer. Falls back to HTTP polling against `rpc_url` when absent.
+    #[serde(default)]
+    ws_url: Option<String>,
     dexes: Vec<Dex>,
     tokens: Tokens,
     min_profit_threshold_usd: f64,
-    fixed_trade_amount_weth: String,
+    fixed_trade_amount_weth: Amount,
+    // Estimated gas units for a single swap on each execution path, so V2
+    // and V3 routes can be priced differently.
+    gas_units_v2: u64,
+    gas_units_v3: u64,
+    // Used to sign transactions when running with `--live`.
+    chain_id: u64,
+    // Maximum acceptable slippage, in basis points, between a trade's quoted
+    // output and its on-chain `amountOutMin` floor.
+    slippage_tolerance_bps: u64,
+    // When set, live trades are submitted as a private bundle to this
+    // relay's `eth_sendBundle` endpoint instead of broadcast individually.
+    #[serde(default)]
+    bundle_rpc_url: Option<String>,
+    // An independent stable/stable pair to monitor alongside the WETH/USDC
+    // route above (e.g. USDC/USDT). This is what actually exercises
+    // `Protocol::StableSwap` dexes for the pair they're meant to price --
+    // the WETH/USDC `dexes` route never configures one.
+    #[serde(default)]
+    stable_route: Option<StableRoute>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StableRoute {
+    // Exactly two dexes quoting the same stable/stable pair.
+    dexes: Vec<Dex>,
+    token_in: Token,
+    token_out: Token,
+    fixed_trade_amount: Amount,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Protocol {
+    V2,
+    V3,
+    StableSwap,
 }
 
 #[derive(Debug, Deserialize)]
 struct Dex {
     name: String,
     router_address: String,
+    protocol: Protocol,
+    // Only required for `Protocol::V3` pools, where the fee tier is part of
+    // the pool identity (e.g. 500 = 0.05%, 3000 = 0.3%).
+    #[serde(default)]
+    pool_fee: Option<u32>,
+    // The WETH/USDC pair address, used to read reserves for optimal
+    // trade-size sizing. Only `Protocol::V2` pairs expose reserves this way.
+    // Also doubles as the pool address for `Protocol::StableSwap`, where
+    // `coins(0)`/`coins(1)` are assumed to match the stable route's
+    // `token_in`/`token_out` ordering.
+    #[serde(default)]
+    pair_address: Option<String>,
+    // The amplification coefficient `A`, required for `Protocol::StableSwap`
+    // pools and unused otherwise.
+    #[serde(default)]
+    amplification: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Token {
+    address: String,
+    decimals: u32,
 }
 
 #[derive(Debug, Deserialize)]
 struct Tokens {
-    usdc: String,
-    weth: String,
+    usdc: Token,
+    weth: Token,
+    // Wrapped MATIC, used only to price gas in USD via the gas oracle.
+    wmatic: Token,
 }
 
+// Whether the bot only prints detected opportunities, or actually submits
+// the trades. Submitting requires both `--live` on the command line and a
+// `PRIVATE_KEY` environment variable.
+const LIVE_FLAG: &str = "--live";
+
 // The main function is the entry point of our program
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     println!("Starting Polygon Arbitrage Bot...");
 
+    let live = std::env::args().any(|arg| arg == LIVE_FLAG);
+
     // 1. Load configuration from files
     let config = load_config().await?;
-    let provider = setup_provider(&config.rpc_url).await?;
-
-    // Convert token addresses from String to the Address type Rust understands
-    let usdc_address = Address::from_str(&config.tokens.usdc)?;
-    let weth_address = Address::from_str(&config.tokens.weth)?;
-    let fixed_trade_amount = config.fixed_trade_amount_weth.parse::<f64>()?;
 
     println!("Bot configured. Monitoring prices...");
+    if live {
+        println!("Running LIVE: detected opportunities will be submitted on-chain.");
+    } else {
+        println!("Running in dry-run mode (pass --live to submit trades).");
+    }
+
+    // 2. Pick the evaluation strategy based on which endpoint is configured:
+    // a WebSocket endpoint drives block-by-block evaluation, an HTTP-only
+    // endpoint falls back to polling on a timer.
+    if let Some(ws_url) = config.ws_url.clone() {
+        run_on_new_blocks(ws_url, config, live).await
+    } else {
+        let provider = setup_provider(&config.rpc_url).await?;
+        let executor = build_executor_if_live(&provider, &config, live).await?;
+        let gas_oracle = gas::build_gas_oracle(provider.clone());
+        run_on_timer(provider, config, executor, gas_oracle).await
+    }
+}
+
+// Builds the signer + nonce-manager client stack when `--live` was passed,
+// otherwise returns `None` and the bot stays in its default print-only mode.
+async fn build_executor_if_live<M: Middleware + 'static>(
+    provider: &Arc<M>,
+    config: &Config,
+    live: bool,
+) -> Result<Option<Arc<ExecutionClient<M>>>, anyhow::Error> {
+    if !live {
+        return Ok(None);
+    }
+    Ok(Some(
+        execution::build_execution_client(provider.clone(), config.chain_id).await?,
+    ))
+}
 
-    // 2. Main loop: runs forever, checking prices every 15 seconds
+// Re-evaluates prices on every new block header. A dropped WebSocket
+// connection is routine for a long-lived subscription, so on stream end this
+// reconnects and keeps monitoring rather than letting the bot exit.
+async fn run_on_new_blocks(ws_url: String, config: Config, live: bool) -> Result<(), anyhow::Error> {
     loop {
-        // Check prices on both DEXes at the same time
-        let (price_dex_a, price_dex_b) = tokio::join!(
-            get_price(&provider, &config.dexes[0], weth_address, usdc_address),
-            get_price(&provider, &config.dexes[1], weth_address, usdc_address)
+        let provider = match Provider::<Ws>::connect(&ws_url).await {
+            Ok(provider) => Arc::new(provider),
+            Err(e) => {
+                println!("Error connecting to WebSocket endpoint: {e}; retrying in 5s.");
+                sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+        let executor = match build_executor_if_live(&provider, &config, live).await {
+            Ok(executor) => executor,
+            Err(e) => {
+                println!("Error building execution client: {e}; retrying in 5s.");
+                sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+        let gas_oracle = gas::build_gas_oracle(provider.clone());
+        let mut blocks = match provider.subscribe_blocks().await {
+            Ok(blocks) => blocks,
+            Err(e) => {
+                println!("Error subscribing to new blocks: {e}; retrying in 5s.");
+                sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        while let Some(block) = blocks.next().await {
+            println!(
+                "New block #{}",
+                block.number.map(|n| n.to_string()).unwrap_or_default()
+            );
+            evaluate_prices(&provider, &config, executor.as_ref(), &gas_oracle).await?;
+        }
+
+        println!("WebSocket block subscription ended; reconnecting.");
+    }
+}
+
+// Re-evaluates prices every 15 seconds, for HTTP-only endpoints that can't
+// push new block notifications.
+async fn run_on_timer(
+    provider: Arc<Provider<Http>>,
+    config: Config,
+    executor: Option<Arc<ExecutionClient<Provider<Http>>>>,
+    gas_oracle: GasOracleClient<Provider<Http>>,
+) -> Result<(), anyhow::Error> {
+    loop {
+        evaluate_prices(&provider, &config, executor.as_ref(), &gas_oracle).await?;
+        sleep(Duration::from_secs(15)).await;
+    }
+}
+
+// Quotes both configured DEXes and, if they're both reachable, checks for an
+// arbitrage opportunity between them. Shared by both the WebSocket and
+// polling evaluation loops. When `executor` is `Some` and an opportunity
+// clears the threshold on a V2/V2 route, submits the trade live.
+async fn evaluate_prices<M: Middleware + 'static>(
+    provider: &Arc<M>,
+    config: &Config,
+    executor: Option<&Arc<ExecutionClient<M>>>,
+    gas_oracle: &GasOracleClient<M>,
+) -> Result<(), anyhow::Error> {
+    let usdc_address = Address::from_str(&config.tokens.usdc.address)?;
+    let weth_address = Address::from_str(&config.tokens.weth.address)?;
+    let wmatic_address = Address::from_str(&config.tokens.wmatic.address)?;
+    let usdc_decimals = config.tokens.usdc.decimals;
+    let weth_decimals = config.tokens.weth.decimals;
+    let wmatic_decimals = config.tokens.wmatic.decimals;
+    let fixed_trade_amount = config.fixed_trade_amount_weth.as_u256();
+
+    // Check prices on both DEXes, and the MATIC/USDC price (off the first
+    // DEX) used to convert gas costs to USD, all at the same time. Prices
+    // are raw USDC base units per one whole unit of `token_in`.
+    let (price_dex_a, price_dex_b, matic_price_base) = tokio::join!(
+        get_price(provider, &config.dexes[0], weth_address, weth_decimals, usdc_address),
+        get_price(provider, &config.dexes[1], weth_address, weth_decimals, usdc_address),
+        get_price(provider, &config.dexes[0], wmatic_address, wmatic_decimals, usdc_address)
+    );
+    let matic_usd_price = matic_price_base
+        .as_ref()
+        .ok()
+        .map(|price| amount::to_display_f64(*price, usdc_decimals));
+
+    // If we got prices from both DEXes, check for arbitrage
+    if let (Ok(price_a), Ok(price_b), Some(matic_usd_price)) = (price_dex_a, price_dex_b, matic_usd_price) {
+        println!(
+            "{}: 1 WETH = {:.2} USDC",
+            config.dexes[0].name,
+            amount::to_display_f64(price_a, usdc_decimals)
+        );
+        println!(
+            "{}: 1 WETH = {:.2} USDC",
+            config.dexes[1].name,
+            amount::to_display_f64(price_b, usdc_decimals)
         );
 
-        // If we got prices from both DEXes, check for arbitrage
-        if let (Ok(price_a), Ok(price_b)) = (price_dex_a, price_dex_b) {
-            println!("{}: 1 WETH = {:.2} USDC", config.dexes[0].name, price_a);
-            println!("{}: 1 WETH = {:.2} USDC", config.dexes[1].name, price_b);
+        // Check which DEX is cheaper
+        let (expensive, cheap) = if price_a > price_b {
+            (&config.dexes[0], &config.dexes[1])
+        } else {
+            (&config.dexes[1], &config.dexes[0])
+        };
 
-            // 3. Check which DEX is cheaper
-            if price_a > price_b {
-                check_arbitrage_opportunity(
-                    &config.dexes[0].name,
-                    price_a,
-                    &config.dexes[1].name,
-                    price_b,
-                    fixed_trade_amount,
-                    config.min_profit_threshold_usd,
+        // Gas cost of the round trip: one swap on the cheap DEX to buy, one
+        // swap on the expensive DEX to sell, each priced by its own route.
+        let (gas_cost_buy, gas_cost_sell) = tokio::join!(
+            gas::estimate_gas_cost_usd(
+                gas_oracle,
+                route_for(&cheap.protocol),
+                config.gas_units_v2,
+                config.gas_units_v3,
+                matic_usd_price,
+            ),
+            gas::estimate_gas_cost_usd(
+                gas_oracle,
+                route_for(&expensive.protocol),
+                config.gas_units_v2,
+                config.gas_units_v3,
+                matic_usd_price,
+            ),
+        );
+        let gas_cost_usd = match (gas_cost_buy, gas_cost_sell) {
+            (Ok(buy), Ok(sell)) => buy + sell,
+            _ => {
+                println!("Error estimating gas cost; skipping this round.");
+                return Ok(());
+            }
+        };
+
+        // Reserve-based optimal sizing only applies to V2 pools: `get_reserves`
+        // below reads `getReserves()`/`token0()`, selectors a StableSwap pool
+        // doesn't expose. StableSwap legs (and any other non-V2 protocol) fall
+        // through to the fixed-trade-amount path instead, which already prices
+        // them correctly since `get_price` dispatches on protocol.
+        let both_v2 = cheap.protocol == Protocol::V2 && expensive.protocol == Protocol::V2;
+        match (both_v2, &cheap.pair_address, &expensive.pair_address) {
+            (true, Some(cheap_pair), Some(expensive_pair)) => {
+                let (reserves_cheap, reserves_expensive) = tokio::join!(
+                    get_reserves(provider, cheap_pair, weth_address),
+                    get_reserves(provider, expensive_pair, weth_address)
                 );
-            } else {
+                match (reserves_cheap, reserves_expensive) {
+                    (
+                        Ok((reserve_weth_cheap, reserve_usdc_cheap)),
+                        Ok((reserve_weth_expensive, reserve_usdc_expensive)),
+                    ) => {
+                        let trade = check_optimal_arbitrage(
+                            &expensive.name,
+                            &cheap.name,
+                            reserve_weth_cheap,
+                            reserve_usdc_cheap,
+                            reserve_weth_expensive,
+                            reserve_usdc_expensive,
+                            usdc_decimals,
+                            gas_cost_usd,
+                            config.min_profit_threshold_usd,
+                        );
+
+                        if let (Some(trade), Some(client)) = (trade, executor) {
+                            if cheap.protocol == Protocol::V2 && expensive.protocol == Protocol::V2 {
+                                match (
+                                    Address::from_str(&cheap.router_address),
+                                    Address::from_str(&expensive.router_address),
+                                ) {
+                                    (Ok(buy_router), Ok(sell_router)) => {
+                                        // A submission failure (dropped tx, revert, RPC
+                                        // hiccup) should not take down the whole bot --
+                                        // log it and keep evaluating on the next tick.
+                                        let result = if let Some(bundle_rpc_url) = &config.bundle_rpc_url {
+                                            execution::execute_v2_arbitrage_bundle(
+                                                client,
+                                                bundle_rpc_url,
+                                                buy_router,
+                                                sell_router,
+                                                usdc_address,
+                                                weth_address,
+                                                trade.amount_in,
+                                                config.slippage_tolerance_bps,
+                                            )
+                                            .await
+                                        } else {
+                                            execution::execute_v2_arbitrage(
+                                                client,
+                                                buy_router,
+                                                sell_router,
+                                                usdc_address,
+                                                weth_address,
+                                                trade.amount_in,
+                                                trade.net_profit,
+                                                config.slippage_tolerance_bps,
+                                            )
+                                            .await
+                                        };
+                                        if let Err(e) = result {
+                                            println!("Error submitting arbitrage trade: {e}");
+                                        }
+                                    }
+                                    _ => println!("Error parsing router addresses; skipping execution."),
+                                }
+                            } else {
+                                println!("Live execution is only wired up for V2/V2 routes; skipping.");
+                            }
+                        }
+                    }
+                    _ => println!("Error fetching reserves from one or more DEXes."),
+                }
+            }
+            (true, _, _) => println!("Error: V2 dex is missing its pair_address."),
+            _ => {
+                let (expensive_price, cheap_price) = if price_a > price_b {
+                    (price_a, price_b)
+                } else {
+                    (price_b, price_a)
+                };
                 check_arbitrage_opportunity(
-                    &config.dexes[1].name,
-                    price_b,
-                    &config.dexes[0].name,
-                    price_a,
+                    &expensive.name,
+                    expensive_price,
+                    &cheap.name,
+                    cheap_price,
                     fixed_trade_amount,
+                    weth_decimals,
+                    usdc_decimals,
+                    gas_cost_usd,
                     config.min_profit_threshold_usd,
                 );
             }
-        } else {
-            // Handle errors if price fetching failed
-            println!("Error fetching prices from one or more DEXes.");
         }
+    } else {
+        // Handle errors if price fetching failed
+        println!("Error fetching prices from one or more DEXes.");
+    }
 
-        // Wait for 15 seconds before checking again
-        sleep(Duration::from_secs(15)).await;
+    if let (Some(stable_route), Some(matic_usd_price)) = (&config.stable_route, matic_usd_price) {
+        evaluate_stable_route(provider, stable_route, matic_usd_price, config.gas_units_v2, config.gas_units_v3, config.min_profit_threshold_usd, gas_oracle).await;
+    }
+
+    Ok(())
+}
+
+// Quotes both dexes in a configured stable/stable route (e.g. USDC/USDT) and
+// checks for an arbitrage opportunity between them, the same way
+// `evaluate_prices` does for the WETH/USDC route above. This is what actually
+// exercises `Protocol::StableSwap` dexes for a genuine stable pair, rather
+// than mislabeling a volatile WETH/USDC leg. Detection-only: reserve-based
+// optimal sizing and live execution are not wired up for this route.
+async fn evaluate_stable_route<M: Middleware + 'static>(
+    provider: &Arc<M>,
+    route: &StableRoute,
+    matic_usd_price: f64,
+    gas_units_v2: u64,
+    gas_units_v3: u64,
+    min_profit_threshold: f64,
+    gas_oracle: &GasOracleClient<M>,
+) {
+    let dex_a = &route.dexes[0];
+    let dex_b = &route.dexes[1];
+    let (token_in_address, token_out_address) =
+        match (Address::from_str(&route.token_in.address), Address::from_str(&route.token_out.address)) {
+            (Ok(token_in), Ok(token_out)) => (token_in, token_out),
+            _ => {
+                println!("Error parsing stable route token addresses.");
+                return;
+            }
+        };
+
+    let (price_a, price_b) = tokio::join!(
+        get_price(provider, dex_a, token_in_address, route.token_in.decimals, token_out_address),
+        get_price(provider, dex_b, token_in_address, route.token_in.decimals, token_out_address),
+    );
+    let (price_a, price_b) = match (price_a, price_b) {
+        (Ok(price_a), Ok(price_b)) => (price_a, price_b),
+        _ => {
+            println!("Error fetching prices from one or more stable-route DEXes.");
+            return;
+        }
+    };
+    println!(
+        "{}: 1 {} = {:.4} {} (stable route)",
+        dex_a.name,
+        route.token_in.address,
+        amount::to_display_f64(price_a, route.token_out.decimals),
+        route.token_out.address
+    );
+    println!(
+        "{}: 1 {} = {:.4} {} (stable route)",
+        dex_b.name,
+        route.token_in.address,
+        amount::to_display_f64(price_b, route.token_out.decimals),
+        route.token_out.address
+    );
+
+    let (expensive, cheap, expensive_price, cheap_price) = if price_a > price_b {
+        (dex_a, dex_b, price_a, price_b)
+    } else {
+        (dex_b, dex_a, price_b, price_a)
+    };
+
+    let (gas_cost_buy, gas_cost_sell) = tokio::join!(
+        gas::estimate_gas_cost_usd(gas_oracle, route_for(&cheap.protocol), gas_units_v2, gas_units_v3, matic_usd_price),
+        gas::estimate_gas_cost_usd(gas_oracle, route_for(&expensive.protocol), gas_units_v2, gas_units_v3, matic_usd_price),
+    );
+    let gas_cost_usd = match (gas_cost_buy, gas_cost_sell) {
+        (Ok(buy), Ok(sell)) => buy + sell,
+        _ => {
+            println!("Error estimating gas cost for stable route; skipping this round.");
+            return;
+        }
+    };
+
+    check_arbitrage_opportunity(
+        &expensive.name,
+        expensive_price,
+        &cheap.name,
+        cheap_price,
+        route.fixed_trade_amount.as_u256(),
+        route.token_in.decimals,
+        route.token_out.decimals,
+        gas_cost_usd,
+        min_profit_threshold,
+    );
+}
+
+// Maps a DEX's protocol to the gas-oracle route used to cost its swaps.
+// StableSwap pools are priced like V2: a single swap call on a single pool
+// contract.
+fn route_for(protocol: &Protocol) -> gas::Route {
+    match protocol {
+        Protocol::V2 | Protocol::StableSwap => gas::Route::V2,
+        Protocol::V3 => gas::Route::V3,
     }
 }
 
@@ -101,52 +505,171 @@ async fn setup_provider(rpc_url: &str) -> Result<Arc<Provider<Http>>, anyhow::Er
     Ok(Arc::new(provider))
 }
 
-// Fetches the price of WETH in USDC from a specific DEX
-// This is a simplified version. A real bot would use the DEX's specific Router contract functions.
-async fn get_price(
-    provider: &Arc<Provider<Http>>,
+// Fetches the price of one whole unit of `token_in` in `token_out`'s base
+// units from a specific DEX, dispatching to the right on-chain call for the
+// DEX's protocol. The result stays in raw base units so callers can compare
+// and combine quotes with integer arithmetic before converting to a display
+// value.
+async fn get_price<M: Middleware + 'static>(
+    provider: &Arc<M>,
     dex: &Dex,
-    _token_in: Address, // WETH
-    _token_out: Address, // USDC
-) -> Result<f64, anyhow::Error> {
-    // SIMPLIFICATION FOR DEMONSTRATION
-    // In reality, here you would use the `ethers::contract` crate to create a contract instance
-    // and call a function on the router contract like `getAmountsOut`.
-    // This requires the specific ABI (Application Binary Interface) of the contract.
-    // For this demo, we'll simulate a price.
-
-    let simulated_price = match dex.name.as_str() {
-        "Uniswap V3" => 3500.0 + (rand::random::<f64>() * 10.0), // e.g., between 3500 and 3510
-        "QuickSwap" => 3500.0 + (rand::random::<f64>() * 10.0),  // e.g., between 3500 and 3510
-        _ => 3500.0,
+    token_in: Address,
+    token_in_decimals: u32,
+    token_out: Address,
+) -> Result<U256, anyhow::Error> {
+    let amount_in = U256::exp10(token_in_decimals as usize); // 1 whole token_in, in base units
+
+    let amount_out = match dex.protocol {
+        Protocol::V2 => {
+            let router_address = Address::from_str(&dex.router_address)?;
+            let router = IUniswapV2Router02::new(router_address, provider.clone());
+            let amounts = router
+                .get_amounts_out(amount_in, vec![token_in, token_out])
+                .call()
+                .await?;
+            *amounts.last().ok_or_else(|| anyhow::anyhow!("empty amounts from getAmountsOut"))?
+        }
+        Protocol::V3 => {
+            let quoter_address = Address::from_str(&dex.router_address)?;
+            let quoter = IUniswapV3Quoter::new(quoter_address, provider.clone());
+            let fee = dex
+                .pool_fee
+                .ok_or_else(|| anyhow::anyhow!("{}: V3 dex requires pool_fee", dex.name))?;
+            quoter
+                .quote_exact_input_single(token_in, token_out, fee, amount_in, U256::zero())
+                .call()
+                .await?
+        }
+        Protocol::StableSwap => {
+            let pool_address = dex
+                .pair_address
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("{}: StableSwap dex requires pair_address", dex.name))?;
+            let amplification = dex
+                .amplification
+                .ok_or_else(|| anyhow::anyhow!("{}: StableSwap dex requires amplification", dex.name))?;
+            let pool = IStableSwapPool::new(Address::from_str(pool_address)?, provider.clone());
+            let (reserve_in, reserve_out) = tokio::try_join!(
+                pool.balances(U256::zero()).call(),
+                pool.balances(U256::one()).call(),
+            )?;
+            stableswap::get_amount_out(U256::from(amplification), reserve_in, reserve_out, amount_in)
+        }
     };
 
-    Ok(simulated_price)
+    Ok(amount_out)
+}
+
+// Reads a V2 pair's reserves and returns them as `(reserve_weth, reserve_usdc)`,
+// regardless of which token is `token0` on-chain.
+async fn get_reserves<M: Middleware + 'static>(
+    provider: &Arc<M>,
+    pair_address: &str,
+    weth_address: Address,
+) -> Result<(U256, U256), anyhow::Error> {
+    let pair = IUniswapV2Pair::new(Address::from_str(pair_address)?, provider.clone());
+    let (reserve0, reserve1, _) = pair.get_reserves().call().await?;
+    let token0 = pair.token_0().call().await?;
+
+    if token0 == weth_address {
+        Ok((U256::from(reserve0), U256::from(reserve1)))
+    } else {
+        Ok((U256::from(reserve1), U256::from(reserve0)))
+    }
 }
 
-// Checks if an arbitrage opportunity exists and logs it
+// Given both pools' reserves, solves for the optimal WETH round-trip size,
+// logs it, and returns the trade when its profit clears the threshold (so
+// the caller can submit it when running live).
+fn check_optimal_arbitrage(
+    expensive_dex: &str,
+    cheap_dex: &str,
+    reserve_weth_cheap: U256,
+    reserve_usdc_cheap: U256,
+    reserve_weth_expensive: U256,
+    reserve_usdc_expensive: U256,
+    usdc_decimals: u32,
+    gas_cost_usd: f64,
+    min_profit_threshold: f64,
+) -> Option<amm::OptimalTrade> {
+    // Buy WETH on the cheap pool (spend USDC, receive WETH), then sell that
+    // WETH on the expensive pool (spend WETH, receive USDC).
+    let trade = amm::find_optimal_trade(
+        reserve_usdc_cheap,
+        reserve_weth_cheap,
+        reserve_weth_expensive,
+        reserve_usdc_expensive,
+    );
+
+    let optimal_amount_usdc = amount::to_display_f64(trade.amount_in, usdc_decimals);
+    let net_profit_usd = amount::to_display_f64(trade.net_profit, usdc_decimals) - gas_cost_usd;
+
+    println!("Estimated Gas Cost: ${:.2}", gas_cost_usd);
+    println!(
+        "Optimal trade size: ${:.2} USDC (buy on {}, sell on {})",
+        optimal_amount_usdc, cheap_dex, expensive_dex
+    );
+    println!("Optimal Net Profit: ${:.2}", net_profit_usd);
+
+    if net_profit_usd > min_profit_threshold {
+        println!("ðŸš€ ARBITRAGE OPPORTUNITY DETECTED!");
+        println!("   Buy  WETH on {} with ${:.2} USDC", cheap_dex, optimal_amount_usdc);
+        println!("   Sell that WETH on {}", expensive_dex);
+        println!("   Estimated Profit: ${:.2}\n", net_profit_usd);
+        Some(trade)
+    } else {
+        println!("No significant opportunity found.\n");
+        None
+    }
+}
+
+// Checks if an arbitrage opportunity exists and logs it. `expensive_price`
+// and `cheap_price` are quote-token base units for one whole unit of the
+// traded token; `trade_amount` is the fixed trade size in the traded token's
+// base units. On the main route those are USDC and WETH; on the stable route
+// they are its `token_out` and `token_in`, though the log lines still label
+// them USDC and WETH. The price-times-
+// amount arithmetic is done in `U256` so it matches what the router
+// contracts themselves would compute, and is only converted to a human `$`
+// figure once, for display and for the threshold comparison.
 fn check_arbitrage_opportunity(
     expensive_dex: &str,
-    expensive_price: f64,
+    expensive_price: U256,
     cheap_dex: &str,
-    cheap_price: f64,
-    trade_amount: f64,
+    cheap_price: U256,
+    trade_amount: U256,
+    weth_decimals: u32,
+    usdc_decimals: u32,
+    gas_cost_usd: f64,
     min_profit_threshold: f64,
 ) {
-    let price_difference = expensive_price - cheap_price;
-    let gross_profit = price_difference * trade_amount;
+    let price_difference = expensive_price.saturating_sub(cheap_price);
+    let weth_scale = U256::exp10(weth_decimals as usize);
+    let gross_profit_base = price_difference * trade_amount / weth_scale;
 
-    // Simplified simulated gas cost (e.g., $2 worth of MATIC)
-    let simulated_gas_cost_usd = 2.0;
-    let net_profit = gross_profit - simulated_gas_cost_usd;
+    let price_difference_usd = amount::to_display_f64(price_difference, usdc_decimals);
+    let gross_profit_usd = amount::to_display_f64(gross_profit_base, usdc_decimals);
+    let net_profit = gross_profit_usd - gas_cost_usd;
+    let trade_amount_weth = amount::to_display_f64(trade_amount, weth_decimals);
 
-    println!("Price Difference: {:.2} USDC per WETH", price_difference);
-    println!("Simulated Net Profit: ${:.2}", net_profit);
+    println!("Price Difference: {:.2} USDC per WETH", price_difference_usd);
+    println!("Estimated Gas Cost: ${:.2}", gas_cost_usd);
+    println!("Net Profit: ${:.2}", net_profit);
 
     if net_profit > min_profit_threshold {
         println!("ðŸš€ ARBITRAGE OPPORTUNITY DETECTED!");
-        println!("   Buy  {} WETH on {} for ${:.2}", trade_amount, cheap_dex, cheap_price * trade_amount);
-        println!("   Sell {} WETH on {} for ${:.2}", trade_amount, expensive_dex, expensive_price * trade_amount);
+        println!(
+            "   Buy  {} WETH on {} for ${:.2}",
+            trade_amount_weth,
+            cheap_dex,
+            amount::to_display_f64(cheap_price, usdc_decimals) * trade_amount_weth
+        );
+        println!(
+            "   Sell {} WETH on {} for ${:.2}",
+            trade_amount_weth,
+            expensive_dex,
+            amount::to_display_f64(expensive_price, usdc_decimals) * trade_amount_weth
+        );
         println!("   Estimated Profit: ${:.2}\n", net_profit);
     } else {
         println!("No significant opportunity found.\n");
@@ -0,0 +1,101 @@
+// Curve-style stableswap invariant math for 2-coin pools, used for
+// stable/stable pairs (e.g. USDC/USDT) where the constant-product formula in
+// `amm.rs` misprices the pool. The invariant is
+// `A*n^n*sum(x_i) + D = A*D*n^n + D^(n+1) / (n^n * prod(x_i))`.
+use ethers::types::U256;
+
+const N_COINS: u32 = 2;
+
+fn n_pow_n() -> U256 {
+    U256::from(N_COINS).pow(U256::from(N_COINS))
+}
+
+/// Solves for the invariant `D` given a 2-coin pool's balances `x, y` and
+/// amplification coefficient `amp`, via Newton iteration starting from
+/// `D = x + y`, until it converges to within 1 unit.
+pub fn compute_d(amp: U256, x: U256, y: U256) -> U256 {
+    let s = x + y;
+    if s.is_zero() {
+        return U256::zero();
+    }
+
+    let n_pow_n = n_pow_n();
+    let ann = amp * U256::from(N_COINS);
+    let mut d = s;
+
+    for _ in 0..255 {
+        // d_p = D^(n+1) / (n^n * prod(x_i)), specialized to n = 2: D^3 / (4*x*y)
+        let d_p = d * d / (x * n_pow_n) * d / y;
+
+        let d_prev = d;
+        let numerator = (ann * s + d_p * U256::from(N_COINS)) * d;
+        let denominator = (ann - U256::one()) * d + U256::from(N_COINS + 1) * d_p;
+        d = numerator / denominator;
+
+        let diff = if d > d_prev { d - d_prev } else { d_prev - d };
+        if diff <= U256::one() {
+            break;
+        }
+    }
+    d
+}
+
+/// Given the pool's new balance `x_new` of one coin, solves for the other
+/// coin's balance `y_new` that keeps the invariant `D` intact, via Newton
+/// iteration on `y^2 + (b - D)*y - c = 0`, starting from `y = D`.
+pub fn compute_y(amp: U256, x_new: U256, d: U256) -> U256 {
+    let n_pow_n = n_pow_n();
+    let ann = amp * U256::from(N_COINS);
+
+    let b = x_new + d / ann;
+    let c = d * d / (x_new * n_pow_n) * d / ann;
+
+    let mut y = d;
+    for _ in 0..255 {
+        let y_prev = y;
+        y = (y * y + c) / (U256::from(2) * y + b - d);
+
+        let diff = if y > y_prev { y - y_prev } else { y_prev - y };
+        if diff <= U256::one() {
+            break;
+        }
+    }
+    y
+}
+
+/// Quotes a stableswap trade: given the current `(reserve_in, reserve_out)`
+/// balances and amplification `amp`, returns the output amount for
+/// `amount_in`, clamped so rounding in the Newton iterations never lets it
+/// exceed the pool's output reserve.
+pub fn get_amount_out(amp: U256, reserve_in: U256, reserve_out: U256, amount_in: U256) -> U256 {
+    let d = compute_d(amp, reserve_in, reserve_out);
+    let new_reserve_in = reserve_in + amount_in;
+    let new_reserve_out = compute_y(amp, new_reserve_in, d);
+    reserve_out.saturating_sub(new_reserve_out).min(reserve_out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reference values hand-computed from this module's own formulas with
+    // `Ann = A * N_COINS` (the convention every deployed Curve-style pool
+    // uses) for a 1,000,000 USDC / 900,000 USDT pool (6 decimals) at A = 100.
+    // Pins the Ann convention so a regression back to `A * n^n` is caught.
+    #[test]
+    fn compute_d_matches_reference() {
+        let x = U256::from(1_000_000u64) * U256::exp10(6);
+        let y = U256::from(900_000u64) * U256::exp10(6);
+        let d = compute_d(U256::from(100u64), x, y);
+        assert_eq!(d, U256::from(1_899_973_873_459u64));
+    }
+
+    #[test]
+    fn get_amount_out_matches_reference() {
+        let reserve_in = U256::from(1_000_000u64) * U256::exp10(6);
+        let reserve_out = U256::from(900_000u64) * U256::exp10(6);
+        let amount_in = U256::from(10_000u64) * U256::exp10(6);
+        let amount_out = get_amount_out(U256::from(100u64), reserve_in, reserve_out, amount_in);
+        assert_eq!(amount_out, U256::from(9_988_466_467u64));
+    }
+}